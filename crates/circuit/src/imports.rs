@@ -14,12 +14,21 @@
 // typically data model classes that are used to identify an object, or for
 // python side casting
 
+use std::collections::HashMap;
+use std::env;
+
+use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::sync::GILOnceCell;
-use pyo3::types::PyTuple;
+use pyo3::types::{PyTuple, PyType};
+
+use ndarray::Array2;
+use num_complex::Complex64;
+use numpy::PyReadonlyArray2;
 
 use crate::operations::{
-    StandardGate, StandardInstruction, STANDARD_GATE_SIZE, STANDARD_INSTRUCTION_SIZE,
+    Operation, OperationRef, Param, StandardGate, StandardInstruction, STANDARD_GATE_SIZE,
+    STANDARD_INSTRUCTION_SIZE,
 };
 
 /// Helper wrapper around `GILOnceCell` instances that are just intended to store a Python object
@@ -99,7 +108,10 @@ pub static CONTROLLED_GATE: ImportOnceCell =
 pub static ANNOTATED_OPERATION: ImportOnceCell =
     ImportOnceCell::new("qiskit.circuit", "AnnotatedOperation");
 pub static DEEPCOPY: ImportOnceCell = ImportOnceCell::new("copy", "deepcopy");
+pub static PICKLE_DUMPS: ImportOnceCell = ImportOnceCell::new("pickle", "dumps");
+pub static PICKLE_LOADS: ImportOnceCell = ImportOnceCell::new("pickle", "loads");
 pub static QI_OPERATOR: ImportOnceCell = ImportOnceCell::new("qiskit.quantum_info", "Operator");
+pub static QISKIT_ERROR: ImportOnceCell = ImportOnceCell::new("qiskit.exceptions", "QiskitError");
 pub static WARNINGS_WARN: ImportOnceCell = ImportOnceCell::new("warnings", "warn");
 pub static CIRCUIT_TO_DAG: ImportOnceCell =
     ImportOnceCell::new("qiskit.converters", "circuit_to_dag");
@@ -130,6 +142,7 @@ pub static XX_EMBODIMENTS: ImportOnceCell =
     ImportOnceCell::new("qiskit.synthesis.two_qubit.xx_decompose", "XXEmbodiments");
 pub static NUMPY_COPY_ONLY_IF_NEEDED: ImportOnceCell =
     ImportOnceCell::new("qiskit._numpy_compat", "COPY_ONLY_IF_NEEDED");
+pub static FUNCTOOLS_PARTIAL: ImportOnceCell = ImportOnceCell::new("functools", "partial");
 
 /// A mapping from the enum variant in crate::operations::StandardGate to the python
 /// module path and class name to import it. This is used to populate the conversion table
@@ -255,24 +268,93 @@ static STDGATE_IMPORT_PATHS: [[&str; 2]; STANDARD_GATE_SIZE] = [
     ["qiskit.circuit.library.standard_gates.x", "RC3XGate"],
 ];
 
-// /// A mapping from the enum variant in crate::operations::StandardInstruction to the python
-// /// module path and class name to import it. This is used to populate the conversion table
-// /// when a gate is added directly via the StandardInstruction path and there isn't a Python object
-// /// to poll the _standard_instruction attribute for.
-// ///
-// /// NOTE: the order here is significant, the StandardInstruction variant's number must match
-// /// index of it's entry in this table. This is all done statically for performance
-// // TODO: replace placeholders with actual implementation
-// static STDINSTRUCTION_IMPORT_PATHS: [[&str; 2]; STANDARD_INSTRUCTION_SIZE] = [
-//     // Barrier = 0
-//     ["qiskit.circuit", "Barrier"],
-//     // Delay = 1
-//     ["qiskit.circuit", "Delay"],
-//     // Measure = 2
-//     ["qiskit.circuit", "Measure"],
-//     // Reset = 3
-//     ["qiskit.circuit", "Reset"],
-// ];
+/// A mapping from the enum variant in crate::operations::StandardGate to the name the gate is
+/// exported under in Terra's OpenQASM 2 `qelib1.inc`. A handful of gates have a Qiskit name that
+/// differs from their `qelib1.inc` name (the canonical case being `C3SXGate`, which is `c3sx` in
+/// Qiskit but `c3sqrtx` in `qelib1.inc`); `None` means the gate has no `qelib1.inc` equivalent
+/// and must be exported as a custom gate definition instead.
+///
+/// NOTE: the order here is significant, the StandardGate variant's number must match
+/// index of it's entry in this table. This is all done statically for performance
+static STDGATE_QASM2_NAMES: [Option<&'static str>; STANDARD_GATE_SIZE] = [
+    None,            // GlobalPhaseGate = 0
+    Some("h"),       // HGate = 1
+    Some("id"),      // IGate = 2
+    Some("x"),       // XGate = 3
+    Some("y"),       // YGate = 4
+    Some("z"),       // ZGate = 5
+    Some("p"),       // PhaseGate = 6
+    None,            // RGate = 7
+    Some("rx"),      // RXGate = 8
+    Some("ry"),      // RYGate = 9
+    Some("rz"),      // RZGate = 10
+    Some("s"),       // SGate = 11
+    Some("sdg"),     // SdgGate = 12
+    Some("sx"),      // SXGate = 13
+    Some("sxdg"),    // SXdgGate = 14
+    Some("t"),       // TGate = 15
+    Some("tdg"),     // TdgGate = 16
+    Some("u"),       // UGate = 17
+    Some("u1"),      // U1Gate = 18
+    Some("u2"),      // U2Gate = 19
+    Some("u3"),      // U3Gate = 20
+    Some("ch"),      // CHGate = 21
+    Some("cx"),      // CXGate = 22
+    Some("cy"),      // CYGate = 23
+    Some("cz"),      // CZGate = 24
+    None,            // DCXGate = 25
+    None,            // ECRGate = 26
+    Some("swap"),    // SwapGate = 27
+    None,            // iSWAPGate = 28
+    Some("cp"),      // CPhaseGate = 29
+    Some("crx"),     // CRXGate = 30
+    Some("cry"),     // CRYGate = 31
+    Some("crz"),     // CRZGate = 32
+    None,            // CSGate = 33
+    None,            // CSdgGate = 34
+    Some("csx"),     // CSXGate = 35
+    Some("cu"),      // CUGate = 36
+    Some("cu1"),     // CU1Gate = 37
+    Some("cu3"),     // CU3Gate = 38
+    Some("rxx"),     // RXXGate = 39
+    None,            // RYYGate = 40
+    Some("rzz"),     // RZZGate = 41
+    None,            // RZXGate = 42
+    None,            // XXMinusYYGate = 43
+    None,            // XXPlusYYGate = 44
+    Some("ccx"),     // CCXGate = 45
+    None,            // CCZGate = 46
+    Some("cswap"),   // CSwapGate = 47
+    Some("rccx"),    // RCCXGate = 48
+    Some("c3x"),     // C3XGate = 49
+    Some("c3sqrtx"), // C3SXGate = 50
+    Some("rc3x"),    // RC3XGate = 51
+];
+
+/// Get the `qelib1.inc` export name for a `StandardGate`, or `None` if the gate has no qelib1.inc
+/// equivalent and must be emitted as a custom gate definition by the OpenQASM 2 exporter.
+#[inline]
+pub fn qasm2_name(rs_gate: StandardGate) -> Option<&'static str> {
+    STDGATE_QASM2_NAMES[rs_gate as usize]
+}
+
+/// A mapping from the enum variant in crate::operations::StandardInstruction to the python
+/// module path and class name to import it. This is used to populate the conversion table
+/// when a gate is added directly via the StandardInstruction path and there isn't a Python object
+/// to poll the _standard_instruction attribute for.
+///
+/// NOTE: the order here is significant, the StandardInstruction variant's number must match
+/// index of it's entry in this table. This is all done statically for performance
+static STDINSTRUCTION_IMPORT_PATHS: [[&str; 2]; STANDARD_INSTRUCTION_SIZE] = [
+    // Barrier = 0
+    ["qiskit.circuit", "Barrier"],
+    // Delay = 1
+    ["qiskit.circuit", "Delay"],
+    // Measure = 2
+    ["qiskit.circuit", "Measure"],
+    // Reset = 3
+    ["qiskit.circuit", "Reset"],
+];
 
 /// A mapping from the enum variant in crate::operations::StandardGate to the python object for the
 /// class that matches it. This is typically used when we need to convert from the internal rust
@@ -283,17 +365,30 @@ static STDGATE_IMPORT_PATHS: [[&str; 2]; STANDARD_GATE_SIZE] = [
 static mut STDGATE_PYTHON_GATES: GILOnceCell<[Option<PyObject>; STANDARD_GATE_SIZE]> =
     GILOnceCell::new();
 
-// /// A mapping from the enum variant in crate::operations::StandardInstruction to the python object for the
-// /// class that matches it. This is typically used when we need to convert from the internal rust
-// /// representation to a Python object for a python user to interact with.
-// ///
-// /// NOTE: the order here is significant it must match the StandardInstruction variant's number must match
-// /// index of it's entry in this table. This is all done statically for performance
-// static mut STDINSTRUCTION_PYTHON_GATES: GILOnceCell<[Option<PyObject>; STANDARD_INSTRUCTION_SIZE]> =
-//     GILOnceCell::new();
+/// A mapping from the enum variant in crate::operations::StandardInstruction to the python object for the
+/// class that matches it. This is typically used when we need to convert from the internal rust
+/// representation to a Python object for a python user to interact with.
+///
+/// NOTE: the order here is significant it must match the StandardInstruction variant's number must match
+/// index of it's entry in this table. This is all done statically for performance
+static mut STDINSTRUCTION_PYTHON_GATES: GILOnceCell<[Option<PyObject>; STANDARD_INSTRUCTION_SIZE]> =
+    GILOnceCell::new();
+
+/// Whether the `QISKIT_NO_CACHE_GATES` environment variable is set, disabling the static
+/// `StandardGate` -> Python class cache. This is read from the environment at most once per
+/// process and memoized, so the per-call check stays a cheap branch rather than a syscall.
+static NO_CACHE_GATES: GILOnceCell<bool> = GILOnceCell::new();
+
+#[inline]
+fn no_cache_gates(py: Python) -> bool {
+    *NO_CACHE_GATES.get_or_init(py, || env::var_os("QISKIT_NO_CACHE_GATES").is_some())
+}
 
 #[inline]
 pub fn populate_std_gate_map(py: Python, rs_gate: StandardGate, py_gate: PyObject) {
+    if no_cache_gates(py) {
+        return;
+    }
     let gate_map = unsafe {
         match STDGATE_PYTHON_GATES.get_mut() {
             Some(gate_map) => gate_map,
@@ -309,27 +404,45 @@ pub fn populate_std_gate_map(py: Python, rs_gate: StandardGate, py_gate: PyObjec
         gate_map[rs_gate as usize] = Some(py_gate.clone_ref(py));
     }
 }
-//
-// #[inline]
-// pub fn populate_std_instruction_map(py: Python, rs_instr: StandardInstruction, py_instr: PyObject) {
-//     let instr_map = unsafe {
-//         match STDINSTRUCTION_PYTHON_GATES.get_mut() {
-//             Some(gate_map) => gate_map,
-//             None => {
-//                 let array: [Option<PyObject>; STANDARD_INSTRUCTION_SIZE] = std::array::from_fn(|_| None);
-//                 STDINSTRUCTION_PYTHON_GATES.set(py, array).unwrap();
-//                 STDINSTRUCTION_PYTHON_GATES.get_mut().unwrap()
-//             }
-//         }
-//     };
-//     let instr_cls = &instr_map[rs_instr as usize];
-//     if instr_cls.is_none() {
-//         instr_map[rs_instr as usize] = Some(py_instr.clone_ref(py));
-//     }
-// }
+
+#[inline]
+fn populate_std_instruction_map(py: Python, index: usize, py_instr: PyObject) {
+    let instr_map = unsafe {
+        match STDINSTRUCTION_PYTHON_GATES.get_mut() {
+            Some(instr_map) => instr_map,
+            None => {
+                let array: [Option<PyObject>; STANDARD_INSTRUCTION_SIZE] =
+                    std::array::from_fn(|_| None);
+                STDINSTRUCTION_PYTHON_GATES.set(py, array).unwrap();
+                STDINSTRUCTION_PYTHON_GATES.get_mut().unwrap()
+            }
+        }
+    };
+    let instr_cls = &instr_map[index];
+    if instr_cls.is_none() {
+        instr_map[index] = Some(py_instr.clone_ref(py));
+    }
+}
+
+/// The index into `STDINSTRUCTION_IMPORT_PATHS` / `STDINSTRUCTION_PYTHON_GATES` for a given
+/// `StandardInstruction`. This has to be derived by hand rather than a bare `as usize` cast
+/// because several variants carry payload data, not just Rust-level fieldless discriminants.
+#[inline]
+fn std_instruction_index(rs_instr: &StandardInstruction) -> usize {
+    match rs_instr {
+        StandardInstruction::Barrier(_) => 0,
+        StandardInstruction::Delay(_, _) => 1,
+        StandardInstruction::Measure => 2,
+        StandardInstruction::Reset => 3,
+    }
+}
 
 #[inline]
 pub fn get_std_gate_class(py: Python, rs_gate: StandardGate) -> PyResult<PyObject> {
+    if no_cache_gates(py) {
+        let [py_mod, py_class] = STDGATE_IMPORT_PATHS[rs_gate as usize];
+        return Ok(py.import_bound(py_mod)?.getattr(py_class)?.unbind());
+    }
     let gate_map =
         unsafe { STDGATE_PYTHON_GATES.get_or_init(py, || std::array::from_fn(|_| None)) };
     let gate = &gate_map[rs_gate as usize];
@@ -365,18 +478,121 @@ pub fn get_std_instruction_types(py: Python) -> &Bound<PyTuple> {
         .bind(py)
 }
 
+/// Resolve the unitary matrix of an arbitrary operation, preferring any statically-known
+/// `StandardGate` matrix and otherwise falling back to `qiskit.quantum_info.Operator`, which
+/// knows how to pull a matrix off a custom `Gate`/`Instruction`/`Operation` subclass.
+///
+/// Returns `None` if the operation genuinely has no matrix to give (e.g. it's parameterized with
+/// an unbound `ParameterExpression`, or `Operator` itself refuses it), rather than raising.
+pub fn operation_matrix(
+    py: Python,
+    op: OperationRef,
+    params: &[Param],
+) -> PyResult<Option<Array2<Complex64>>> {
+    if let Some(matrix) = op.matrix(params) {
+        return Ok(Some(matrix));
+    }
+    let py_op = match op {
+        OperationRef::Gate(gate) => gate.gate.bind(py).clone(),
+        OperationRef::Instruction(instruction) => instruction.instruction.bind(py).clone(),
+        OperationRef::Operation(operation) => operation.operation.bind(py).clone(),
+        // Standard gates/instructions either have a matrix already handled above, or (like
+        // `Barrier`/`Measure`/`Reset`) have no unitary to give.
+        OperationRef::Standard(_) | OperationRef::StandardInstruction(_) => return Ok(None),
+    };
+    let operator = match QI_OPERATOR.get_bound(py).call1((py_op,)) {
+        Ok(operator) => operator,
+        // `Operator.__init__` raises `QiskitError` (or a subclass, e.g. `CircuitError`) when the
+        // operation genuinely has no matrix to give, such as an unbound `ParameterExpression`.
+        // Anything else (a real bug surfaced from a broken custom `__array__`, `KeyboardInterrupt`,
+        // `MemoryError`, ...) must propagate instead of being masked as "no matrix available".
+        Err(err)
+            if err
+                .value_bound(py)
+                .is_instance(QISKIT_ERROR.get_bound(py))
+                .unwrap_or(false) =>
+        {
+            return Ok(None)
+        }
+        Err(err) => return Err(err),
+    };
+    let data: PyReadonlyArray2<Complex64> = operator.getattr(intern!(py, "data"))?.extract()?;
+    Ok(Some(data.as_array().to_owned()))
+}
+
+/// A cache mapping the `id()` of each imported standard-gate Python class back to its
+/// `StandardGate` variant. This is the inverse of `STDGATE_IMPORT_PATHS`, lazily built the first
+/// time it's needed so that circuit-construction code can classify an arbitrary Python gate
+/// object in O(1) without calling back into Python to poll a `_standard_gate` attribute.
+static STDGATE_FROM_PY_CLASS: GILOnceCell<HashMap<usize, StandardGate>> = GILOnceCell::new();
+
+fn std_gate_from_py_class_map(py: Python) -> PyResult<&HashMap<usize, StandardGate>> {
+    if let Some(map) = STDGATE_FROM_PY_CLASS.get(py) {
+        return Ok(map);
+    }
+    let mut map = HashMap::with_capacity(STANDARD_GATE_SIZE);
+    for (index, [py_mod, py_class]) in STDGATE_IMPORT_PATHS.iter().enumerate() {
+        let gate_cls = py.import_bound(py_mod)?.getattr(*py_class)?;
+        // SAFETY: `index` is in bounds for `StandardGate` because it was derived from iterating
+        // `STDGATE_IMPORT_PATHS`, which has exactly `STANDARD_GATE_SIZE` entries, one per variant.
+        let rs_gate: StandardGate = unsafe { std::mem::transmute(index as u8) };
+        // SAFETY: `gate_cls` is a reference-counted Python class object that lives at least as
+        // long as its defining module, which is never unloaded; the pointer is stable and only
+        // used as an opaque key, never dereferenced.
+        map.insert(gate_cls.as_ptr() as usize, rs_gate);
+    }
+    Ok(STDGATE_FROM_PY_CLASS.get_or_init(py, || map))
+}
+
+/// Given a Python gate object (or its class), identify which `StandardGate` variant it is, so it
+/// can be packed inline into a `PackedOperation` rather than boxed as a `PyGate`.
+pub fn try_standard_gate_from_py(py: Python, gate: &Bound<PyAny>) -> Option<StandardGate> {
+    let gate_cls = if gate.is_instance_of::<PyType>() {
+        gate.clone()
+    } else {
+        gate.get_type()
+    };
+    std_gate_from_py_class_map(py)
+        .ok()?
+        .get(&(gate_cls.as_ptr() as usize))
+        .copied()
+}
+
+/// Get the Python class (or a callable standing in for it) that corresponds to a
+/// `StandardInstruction`, statically caching the imported class the same way
+/// `get_std_gate_class` does for `StandardGate`.
+///
+/// Unlike a bare gate class, the returned object already has the instruction's payload baked
+/// in: a `Barrier` comes back as a callable that only needs to be called with no further
+/// arguments to produce an `n`-qubit barrier, and a `Delay` comes back carrying its duration and
+/// unit. This lets callers construct the Python-space object without re-plumbing
+/// num_qubits/duration/unit through every call site.
 #[inline]
 pub fn get_std_instruction_class(py: Python, rs_instr: StandardInstruction) -> PyResult<PyObject> {
+    let instr_map =
+        unsafe { STDINSTRUCTION_PYTHON_GATES.get_or_init(py, || std::array::from_fn(|_| None)) };
+    let index = std_instruction_index(&rs_instr);
+    let instr_cls = &instr_map[index];
+    let populate = instr_cls.is_none();
+    let base_cls = match instr_cls {
+        Some(instr_cls) => instr_cls.clone_ref(py),
+        None => {
+            let [py_mod, py_class] = STDINSTRUCTION_IMPORT_PATHS[index];
+            py.import_bound(py_mod)?.getattr(py_class)?.unbind()
+        }
+    };
+    if populate {
+        populate_std_instruction_map(py, index, base_cls.clone_ref(py));
+    }
+    let partial = FUNCTOOLS_PARTIAL.get_bound(py);
     Ok(match rs_instr {
-        StandardInstruction::Barrier(_) => {
-            // TODO: bake in num gates by returning a custom callable?
-            BARRIER.get_bound(py).unbind()
+        StandardInstruction::Barrier(num_qubits) => {
+            partial.call1((base_cls, num_qubits))?.unbind()
         }
-        StandardInstruction::Delay(_, _) => {
-            // TODO: bake in parameters like duration by returning a custom callable?
-            DELAY.get_bound(py).unbind()
+        StandardInstruction::Delay(duration, unit) => {
+            partial.call1((base_cls, duration, unit))?.unbind()
         }
-        StandardInstruction::Measure => MEASURE.get_bound(py).unbind(),
-        StandardInstruction::Reset => RESET.get_bound(py).unbind(),
+        StandardInstruction::Measure => base_cls,
+        StandardInstruction::Reset => base_cls,
     })
 }
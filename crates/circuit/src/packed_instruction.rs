@@ -15,10 +15,11 @@ use bitfield_struct::bitfield;
 use std::cell::OnceCell;
 use std::fmt;
 use std::ptr::NonNull;
+use std::sync::Arc;
 
 use pyo3::intern;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyType};
+use pyo3::types::{PyBytes, PyDict, PyType};
 
 use ndarray::Array2;
 use num_complex::Complex64;
@@ -26,7 +27,10 @@ use smallvec::SmallVec;
 
 use crate::circuit_data::CircuitData;
 use crate::circuit_instruction::ExtraInstructionAttributes;
-use crate::imports::{get_std_gate_class, BARRIER, DEEPCOPY, DELAY, MEASURE, RESET};
+use crate::imports::{
+    get_std_gate_class, get_std_instruction_class, try_standard_gate_from_py, BARRIER, DEEPCOPY,
+    DELAY, MEASURE, PICKLE_DUMPS, PICKLE_LOADS, RESET,
+};
 use crate::interner::Interned;
 use crate::operations::{
     DelayUnit, Operation, OperationRef, Param, PyGate, PyInstruction, PyOperation, StandardGate,
@@ -46,10 +50,108 @@ enum PackedOperationType {
     PyGatePointer = 2,
     PyInstructionPointer = 3,
     PyOperationPointer = 4,
-    // Remember to update PackedOperationType::is_valid_bit_pattern below
-    // if you add or remove this enum's variants!
+    StandardGateInlineAngle = 5,
+    // `discriminant` is asserted elsewhere to be exactly 3 bits wide across every `BitField`
+    // union member (see `BitField::_CHECK`), so only 8 discriminant values ever exist, 6 of
+    // which are already spoken for above. An `Arc`-backed (rather than `Box`-owned) pointer
+    // payload is distinguished by its own discriminant value rather than a separate flag bit,
+    // since a flag bit would need a pointer alignment (16 bytes) that isn't guaranteed for
+    // `PyGate`/`PyInstruction`/`PyOperation` the way 8-byte alignment is. That leaves exactly
+    // two free values, so only `PyGate`/`PyInstruction` get a shared variant; `PyOperationPointer`
+    // (the least commonly duplicated of the three in practice) stays `Box`-only for now.
+    PyGatePointerShared = 6,
+    PyInstructionPointerShared = 7,
+    // Remember to update PackedOperationType::from_bits below if you add or remove variants --
+    // there are no discriminant values left to spare.
+}
+
+/// A coarse classification of what kind of operation a `PackedOperation` performs.
+///
+/// This mirrors the category/ISA-set tagging used by instruction decoders to let a caller filter
+/// a stream of operations without string-comparing `op.name()`.  For the inline discriminants
+/// (`StandardGate`, `StandardGateInlineAngle`, `StandardInstruction`) this resolves from a static
+/// table keyed on the inline opcode byte, so classification never needs to touch Python.  A boxed
+/// `PyGate` is classified by looking up its Python class among the standard-gate classes; a boxed
+/// `PyInstruction` or `PyOperation` is not queried and always classifies as `Custom`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpCategory {
+    /// A gate from the Clifford group (e.g. `H`, `CX`, `S`), closed under conjugation of Paulis.
+    CliffordGate,
+    /// A gate parameterized by one or more continuous rotation angles.
+    Rotation,
+    /// Any other unitary `StandardGate` that isn't classified as `CliffordGate` or `Rotation`.
+    OtherGate,
+    /// A projective measurement.
+    Measurement,
+    /// A qubit reset.
+    Reset,
+    /// A scheduling barrier.
+    Barrier,
+    /// A timed delay.
+    Delay,
+    /// A custom Python-defined gate, instruction, or operation.
+    Custom,
 }
 
+/// The static classification of each `StandardGate`, indexed by its discriminant.
+///
+/// NOTE: the order here must match the order of the `StandardGate` enum and `STDGATE_IMPORT_PATHS`
+/// in `imports.rs`.
+static STDGATE_CATEGORIES: [OpCategory; STANDARD_GATE_SIZE] = [
+    OpCategory::OtherGate,     // GlobalPhaseGate = 0
+    OpCategory::CliffordGate,  // HGate = 1
+    OpCategory::CliffordGate,  // IGate = 2
+    OpCategory::CliffordGate,  // XGate = 3
+    OpCategory::CliffordGate,  // YGate = 4
+    OpCategory::CliffordGate,  // ZGate = 5
+    OpCategory::Rotation,      // PhaseGate = 6
+    OpCategory::Rotation,      // RGate = 7
+    OpCategory::Rotation,      // RXGate = 8
+    OpCategory::Rotation,      // RYGate = 9
+    OpCategory::Rotation,      // RZGate = 10
+    OpCategory::CliffordGate,  // SGate = 11
+    OpCategory::CliffordGate,  // SdgGate = 12
+    OpCategory::CliffordGate,  // SXGate = 13
+    OpCategory::CliffordGate,  // SXdgGate = 14
+    OpCategory::OtherGate,     // TGate = 15
+    OpCategory::OtherGate,     // TdgGate = 16
+    OpCategory::Rotation,      // UGate = 17
+    OpCategory::Rotation,      // U1Gate = 18
+    OpCategory::Rotation,      // U2Gate = 19
+    OpCategory::Rotation,      // U3Gate = 20
+    OpCategory::OtherGate,     // CHGate = 21
+    OpCategory::CliffordGate,  // CXGate = 22
+    OpCategory::CliffordGate,  // CYGate = 23
+    OpCategory::CliffordGate,  // CZGate = 24
+    OpCategory::CliffordGate,  // DCXGate = 25
+    OpCategory::CliffordGate,  // ECRGate = 26
+    OpCategory::CliffordGate,  // SwapGate = 27
+    OpCategory::CliffordGate,  // iSwapGate = 28
+    OpCategory::Rotation,      // CPhaseGate = 29
+    OpCategory::Rotation,      // CRXGate = 30
+    OpCategory::Rotation,      // CRYGate = 31
+    OpCategory::Rotation,      // CRZGate = 32
+    OpCategory::OtherGate,     // CSGate = 33
+    OpCategory::OtherGate,     // CSdgGate = 34
+    OpCategory::OtherGate,     // CSXGate = 35
+    OpCategory::Rotation,      // CUGate = 36
+    OpCategory::Rotation,      // CU1Gate = 37
+    OpCategory::Rotation,      // CU3Gate = 38
+    OpCategory::Rotation,      // RXXGate = 39
+    OpCategory::Rotation,      // RYYGate = 40
+    OpCategory::Rotation,      // RZZGate = 41
+    OpCategory::Rotation,      // RZXGate = 42
+    OpCategory::Rotation,      // XXMinusYYGate = 43
+    OpCategory::Rotation,      // XXPlusYYGate = 44
+    OpCategory::OtherGate,     // CCXGate = 45
+    OpCategory::OtherGate,     // CCZGate = 46
+    OpCategory::OtherGate,     // CSwapGate = 47
+    OpCategory::OtherGate,     // RCCXGate = 48
+    OpCategory::OtherGate,     // C3XGate = 49
+    OpCategory::OtherGate,     // C3SXGate = 50
+    OpCategory::OtherGate,     // RC3XGate = 51
+];
+
 /// A bit-packed `OperationType` enumeration.
 ///
 /// This is logically equivalent to:
@@ -103,6 +205,13 @@ enum PackedOperationType {
 ///    retrieve the "full" pointer by taking the whole `usize` and zeroing     is 0b011, which means
 ///    the low 3 bits, letting us store the discriminant in there at other     that this points to
 ///    times.                                                                  a `PyInstruction`.
+///
+/// Standard gate with an inline angle:
+/// 0b_AAAAAAAA_AAAAAAAA_AAAAAAAA_AAAAAAAA_xxxxxxxx_xxxxxxxx_xxxxxSSS_SSSSS101
+///    |---------------------------------|                        |-------||-|
+///                     |                                             |     |
+///                     +-- A single f32 Euler angle.                 |     +-- Discriminant.
+///                           Standard gate, stored inline as a u8. --+
 /// ```
 ///
 /// # Construction
@@ -136,6 +245,9 @@ impl fmt::Debug for BitField {
             PackedOperationType::PyGatePointer => unsafe { self.pointer }.fmt(f),
             PackedOperationType::PyInstructionPointer => unsafe { self.pointer }.fmt(f),
             PackedOperationType::PyOperationPointer => unsafe { self.pointer }.fmt(f),
+            PackedOperationType::StandardGateInlineAngle => unsafe { self.angle }.fmt(f),
+            PackedOperationType::PyGatePointerShared => unsafe { self.pointer }.fmt(f),
+            PackedOperationType::PyInstructionPointerShared => unsafe { self.pointer }.fmt(f),
         }
     }
 }
@@ -145,6 +257,7 @@ union BitField {
     gate: StandardGateBits,
     instruction: StandardInstructionBits,
     pointer: PointerBits,
+    angle: StandardGateInlineAngleBits,
 }
 
 impl BitField {
@@ -171,6 +284,12 @@ impl BitField {
                 == Self::DISCRIMINANT_MASK,
             "(PointerBits) discriminant MUST be the 3 lowest bits!"
         );
+        assert!(
+            ((1 << StandardGateInlineAngleBits::DISCRIMINANT_BITS) - 1)
+                << StandardGateInlineAngleBits::DISCRIMINANT_OFFSET
+                == Self::DISCRIMINANT_MASK,
+            "(StandardGateInlineAngleBits) discriminant MUST be the 3 lowest bits!"
+        );
     };
 }
 
@@ -192,6 +311,12 @@ impl From<PointerBits> for BitField {
     }
 }
 
+impl From<StandardGateInlineAngleBits> for BitField {
+    fn from(angle: StandardGateInlineAngleBits) -> Self {
+        Self { angle }
+    }
+}
+
 // #[bitfield(u64)]
 // #[derive(PartialEq, Eq)]
 // struct OpBitField {
@@ -272,6 +397,9 @@ impl PackedOperationType {
             2 => Self::PyGatePointer,
             3 => Self::PyInstructionPointer,
             4 => Self::PyOperationPointer,
+            5 => Self::StandardGateInlineAngle,
+            6 => Self::PyGatePointerShared,
+            7 => Self::PyInstructionPointerShared,
             _ => panic!("unexpected discriminant type!"),
         }
     }
@@ -299,11 +427,47 @@ struct StandardInstructionBits {
     payload: ImmediateValue,
 }
 
+/// A `StandardGate` with exactly one numeric parameter packed inline as the bits of an `f32`
+/// Euler angle, instead of requiring a separate out-of-line parameter vector.  This is only used
+/// for the common case of a concrete (non-symbolic) single-angle rotation; gates with symbolic
+/// `ParameterExpression`s or more than one parameter still use the general `StandardGateBits`
+/// representation with the angle(s) stored out-of-line on the owning `PackedInstruction`.
+#[bitfield(u64)]
+struct StandardGateInlineAngleBits {
+    #[bits(3, default = PackedOperationType::StandardGateInlineAngle, access = RO)]
+    discriminant: PackedOperationType,
+    #[bits(8)]
+    standard_gate: StandardGate,
+    #[bits(21)]
+    _pad1: u32,
+    #[bits(32)]
+    angle: InlineAngle,
+}
+
+/// A single `f32` Euler angle, bit-packed as the 32-bit payload of a `StandardGateInlineAngleBits`.
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+struct InlineAngle(f32);
+
+impl InlineAngle {
+    const fn into_bits(self) -> u32 {
+        self.0.to_bits()
+    }
+
+    const fn from_bits(value: u32) -> Self {
+        Self(f32::from_bits(value))
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 #[repr(transparent)]
 struct ImmediateValue(u32);
 
 impl ImmediateValue {
+    /// The largest delay duration (in whatever unit it's expressed) that can be packed inline.
+    /// The low 24 bits of the payload hold the duration; the high 8 hold the `DelayUnit`.
+    const MAX_INLINE_DELAY_DURATION: u32 = (1 << 24) - 1;
+
     const fn into_bits(self) -> u32 {
         self.0
     }
@@ -312,14 +476,36 @@ impl ImmediateValue {
         Self(value)
     }
 
+    /// Pack a concrete, non-negative, integral delay duration together with its unit, returning
+    /// `None` if `duration` doesn't fit in the available 24 bits. Callers should fall back to an
+    /// out-of-line `Param` representation of the duration in that case.
+    #[inline]
+    fn from_delay(duration: u32, unit: DelayUnit) -> Option<Self> {
+        if duration > Self::MAX_INLINE_DELAY_DURATION {
+            return None;
+        }
+        Some(Self(duration | ((unit as u32) << 24)))
+    }
+
     #[inline]
-    fn from_delay_unit(unit: DelayUnit) -> Self {
-        Self(unit as u32)
+    fn delay_duration(&self) -> u32 {
+        self.0 & Self::MAX_INLINE_DELAY_DURATION
     }
 
     #[inline]
     fn delay_unit(&self) -> DelayUnit {
-        todo!()
+        let raw = (self.0 >> 24) as u8;
+        [
+            DelayUnit::DT,
+            DelayUnit::NS,
+            DelayUnit::US,
+            DelayUnit::MS,
+            DelayUnit::S,
+            DelayUnit::PS,
+        ]
+        .into_iter()
+        .find(|unit| *unit as u8 == raw)
+        .unwrap_or_else(|| panic!("ImmediateValue contains an unrecognized DelayUnit discriminant: {raw}"))
     }
 
     #[inline]
@@ -328,6 +514,54 @@ impl ImmediateValue {
     }
 }
 
+/// Try to pull a concrete, non-negative, integral delay duration out of `duration` that's small
+/// enough to fit in `ImmediateValue`'s 24-bit duration field. Returns `None` for a symbolic
+/// `ParameterExpression`, a negative or non-integral duration, or one that's simply too large;
+/// callers should fall back to storing `duration` out-of-line as a `Param` in that case.
+#[inline]
+fn inline_delay_duration(duration: &Param) -> Option<u32> {
+    match duration {
+        Param::Float(value) if value.fract() == 0.0 => {
+            if *value < 0.0 || *value > ImmediateValue::MAX_INLINE_DELAY_DURATION as f64 {
+                None
+            } else {
+                Some(*value as u32)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Build the boxed `PyInstruction` fallback for a `Delay` whose duration couldn't be packed
+/// inline, by constructing a real `qiskit.circuit.Delay` from `duration`/`unit` the same way
+/// Python-space code would.
+fn boxed_delay(duration: Param, unit: DelayUnit) -> PyInstruction {
+    Python::with_gil(|py| {
+        let ctor = get_std_instruction_class(py, StandardInstruction::Delay(duration, unit))
+            .expect("qiskit.circuit.Delay must always be importable");
+        let instruction = ctor
+            .call0(py)
+            .expect("constructing a Delay from its own (duration, unit) must not fail");
+        PyInstruction {
+            instruction,
+            qubits: 1,
+            clbits: 0,
+            params: 1,
+            control_flow: false,
+            op_name: "delay".to_string(),
+        }
+    })
+}
+
+// `PointerBits` packs only its discriminant into the low 3 bits of the pointer, matching every
+// other `BitField` union member (see `BitField::_CHECK`), which is sound for `Box<PyGate>`/
+// `Arc<PyGate>` (and friends) as long as they're at least 8-byte aligned. On 64-bit hosts this
+// holds for free because every one of these types embeds a `Py<PyAny>`, which is pointer-sized;
+// on 32-bit/`wasm32` hosts a bare pointer is only 4-byte aligned, which is why `with_pointer`
+// below still only asserts the 3-bit (8-byte) invariant rather than assuming a wider one.
+// Whether the payload is `Arc`-shared rather than uniquely `Box`-owned is therefore encoded as
+// its own discriminant value (`PyGatePointerShared`/`PyInstructionPointerShared`) instead of a
+// 4th flag bit, since a 4th bit would require 16-byte pointee alignment that isn't guaranteed.
 #[bitfield(u64, new = false)]
 struct PointerBits {
     #[bits(3, access = RO)]
@@ -344,6 +578,29 @@ const fn u64_from_address(value: u64) -> u64 {
     value >> 3
 }
 
+// The whole pointer-tagging scheme above is only sound if `Box<PyGate>`/`Arc<PyGate>` (and
+// friends) are at least 8-byte aligned, since that's what leaves the low 3 bits free for the
+// discriminant. On 64-bit targets this follows from each of these types embedding a pointer-sized
+// `Py<PyAny>`; on 32-bit targets that field is only 4-byte aligned, so it depends on the types'
+// other fields. It's the load-bearing invariant the rest of this module assumes, so check it at
+// compile time on every build (failing the build where it doesn't hold) rather than only in a
+// 32-bit-gated test.
+#[allow(clippy::assertions_on_constants)]
+const _POINTER_ALIGNMENT_CHECK: () = {
+    assert!(
+        std::mem::align_of::<PyGate>() >= 8,
+        "PyGate must be at least 8-byte aligned so its low 3 bits are free for the discriminant"
+    );
+    assert!(
+        std::mem::align_of::<PyInstruction>() >= 8,
+        "PyInstruction must be at least 8-byte aligned so its low 3 bits are free for the discriminant"
+    );
+    assert!(
+        std::mem::align_of::<PyOperation>() >= 8,
+        "PyOperation must be at least 8-byte aligned so its low 3 bits are free for the discriminant"
+    );
+};
+
 impl PointerBits {
     fn new(discriminant: PackedOperationType) -> Self {
         if !matches!(
@@ -351,12 +608,18 @@ impl PointerBits {
             PackedOperationType::PyGatePointer
                 | PackedOperationType::PyInstructionPointer
                 | PackedOperationType::PyOperationPointer
+                | PackedOperationType::PyGatePointerShared
+                | PackedOperationType::PyInstructionPointerShared
         ) {
             panic!("discriminant not valid for pointer!")
         }
         Self::from_bits(discriminant as u64)
     }
 
+    // `address` is always a `u64` regardless of host pointer width: on a 32-bit (or `wasm32`)
+    // target a `usize` pointer only ever occupies the low 32 bits of it, so `as` casts between
+    // `u64` and the native pointer type below do the right narrowing/widening for free and no
+    // separate 32-bit representation is needed.
     #[inline]
     const fn pointer(&self) -> NonNull<()> {
         let ptr = self.address() as *mut ();
@@ -370,20 +633,16 @@ impl PointerBits {
         assert_eq!(addr & BitField::DISCRIMINANT_MASK, 0);
         self.with_address(addr)
     }
-}
-
-#[cfg(target_pointer_width = "32")]
-impl OpBitField {
-    #[inline]
-    unsafe fn pointer(&self) -> NonNull<()> {
-        let ptr = self.payload().u32 as *mut ();
-        NonNull::new_unchecked(ptr)
-    }
 
+    /// Whether this pointer's discriminant marks it as `Arc`-shared rather than uniquely
+    /// `Box`-owned.
     #[inline]
-    unsafe fn with_pointer(self, value: NonNull<()>) -> Self {
-        let addr = value.as_ptr() as u32;
-        self.with_payload(OpPayload { u32: addr })
+    fn is_shared(&self) -> bool {
+        matches!(
+            self.discriminant(),
+            PackedOperationType::PyGatePointerShared
+                | PackedOperationType::PyInstructionPointerShared
+        )
     }
 }
 
@@ -428,6 +687,22 @@ impl PackedOperation {
     pub fn try_standard_gate(&self) -> Option<StandardGate> {
         match self.discriminant() {
             PackedOperationType::StandardGate => Some(unsafe { self.0.gate.standard_gate() }),
+            PackedOperationType::StandardGateInlineAngle => {
+                Some(unsafe { self.0.angle.standard_gate() })
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the contained inline `f32` Euler angle, if this `PackedOperation` holds a
+    /// single-parameter `StandardGate` whose angle is packed directly inline rather than stored
+    /// out-of-line on the owning `PackedInstruction`.
+    #[inline]
+    pub fn try_inline_angle(&self) -> Option<f32> {
+        match self.discriminant() {
+            PackedOperationType::StandardGateInlineAngle => {
+                Some(unsafe { self.0.angle.angle() }.0)
+            }
             _ => None,
         }
     }
@@ -453,7 +728,11 @@ impl PackedOperation {
                         StandardInstruction::Barrier(instruction.payload().u32() as usize)
                     }
                     StandardInstructionType::Delay => {
-                        StandardInstruction::Delay(instruction.payload().delay_unit())
+                        let payload = instruction.payload();
+                        StandardInstruction::Delay(
+                            Param::Float(payload.delay_duration() as f64),
+                            payload.delay_unit(),
+                        )
                     }
                     StandardInstructionType::Measure => StandardInstruction::Measure,
                     StandardInstructionType::Reset => StandardInstruction::Reset,
@@ -467,15 +746,18 @@ impl PackedOperation {
     #[inline]
     pub fn view(&self) -> OperationRef {
         match self.discriminant() {
-            PackedOperationType::StandardGate => OperationRef::Standard(self.standard_gate()),
+            PackedOperationType::StandardGate | PackedOperationType::StandardGateInlineAngle => {
+                OperationRef::Standard(self.standard_gate())
+            }
             PackedOperationType::StandardInstruction => {
                 OperationRef::StandardInstruction(self.standard_instruction())
             }
-            PackedOperationType::PyGatePointer => {
+            PackedOperationType::PyGatePointer | PackedOperationType::PyGatePointerShared => {
                 let ptr = unsafe { self.0.pointer }.pointer().cast::<PyGate>();
                 OperationRef::Gate(unsafe { ptr.as_ref() })
             }
-            PackedOperationType::PyInstructionPointer => {
+            PackedOperationType::PyInstructionPointer
+            | PackedOperationType::PyInstructionPointerShared => {
                 let ptr = unsafe { self.0.pointer }.pointer().cast::<PyInstruction>();
                 OperationRef::Instruction(unsafe { ptr.as_ref() })
             }
@@ -492,8 +774,52 @@ impl PackedOperation {
         Self(StandardGateBits::new().with_standard_gate(standard).into())
     }
 
+    /// Create a `PackedOperation` from a `StandardGate` and a single concrete `f32` Euler angle,
+    /// packing the angle directly inline instead of requiring a separate out-of-line parameter
+    /// vector on the owning `PackedInstruction`. Callers are responsible for only using this for
+    /// gates that take exactly one parameter, and for falling back to `from_standard` (with the
+    /// angle stored out-of-line as a `Param`) for symbolic `ParameterExpression`s or gates with
+    /// more than one parameter.
+    #[inline]
+    pub fn from_standard_inline_angle(standard: StandardGate, angle: f32) -> Self {
+        Self(
+            StandardGateInlineAngleBits::new()
+                .with_standard_gate(standard)
+                .with_angle(InlineAngle(angle))
+                .into(),
+        )
+    }
+
+    /// Create a `PackedOperation` from a `StandardGate` and its full parameter list, choosing
+    /// between the inline-angle and out-of-line representations the same way
+    /// `from_standard_instruction` chooses for a `Delay`'s duration.
+    ///
+    /// If `params` is exactly one concrete `Param::Float`, the angle is packed directly inline
+    /// (see `from_standard_inline_angle`) so the owning `PackedInstruction` needs no separate
+    /// parameter vector at all. Any other case -- a symbolic `ParameterExpression`, zero
+    /// parameters, or more than one -- falls back to the existing out-of-line representation: a
+    /// bare `StandardGate`, with `params` kept out-of-line on the caller's `PackedInstruction` the
+    /// way it always has been.
+    pub fn from_standard_with_params(standard: StandardGate, params: &[Param]) -> Self {
+        match params {
+            [Param::Float(angle)] => Self::from_standard_inline_angle(standard, *angle as f32),
+            _ => Self::from_standard(standard),
+        }
+    }
+
     /// Create a `PackedOperation` from a `StandardInstruction`.
+    ///
+    /// A `StandardInstruction::Delay` whose duration is a concrete, non-negative, integral value
+    /// that fits in the inline 24-bit field is packed entirely inline. Any other duration (a
+    /// symbolic `ParameterExpression`, a negative/non-integral value, or one simply too large)
+    /// falls back to the existing out-of-line representation: a boxed `PyInstruction` wrapping a
+    /// real `qiskit.circuit.Delay`, the same as any other custom operation.
     pub fn from_standard_instruction(instruction: StandardInstruction) -> Self {
+        if let StandardInstruction::Delay(duration, unit) = &instruction {
+            if inline_delay_duration(duration).is_none() {
+                return Self::from_instruction(Box::new(boxed_delay(duration.clone(), *unit)));
+            }
+        }
         let mut bits = StandardInstructionBits::new();
         match instruction {
             StandardInstruction::Barrier(num_qubits) => {
@@ -504,10 +830,16 @@ impl PackedOperation {
                     .with_standard_instruction(StandardInstructionType::Barrier)
                     .with_payload(ImmediateValue(num_qubits))
             }
-            StandardInstruction::Delay(unit) => {
+            StandardInstruction::Delay(duration, unit) => {
+                let payload = inline_delay_duration(&duration)
+                    .and_then(|ticks| ImmediateValue::from_delay(ticks, unit))
+                    .expect(
+                        "unreachable: non-inlinable delays already returned via the out-of-line \
+                         fallback above"
+                    );
                 bits = bits
                     .with_standard_instruction(StandardInstructionType::Delay)
-                    .with_payload(ImmediateValue::from_delay_unit(unit))
+                    .with_payload(payload)
             }
             StandardInstruction::Measure => {
                 bits = bits.with_standard_instruction(StandardInstructionType::Measure);
@@ -529,6 +861,20 @@ impl PackedOperation {
         )
     }
 
+    /// Construct a new `PackedOperation` that shares ownership of `gate` with any other
+    /// `PackedOperation` created from the same `Arc`, via reference counting rather than a deep
+    /// copy. Use [`Self::make_unique`] to detach a private copy before mutating it.
+    pub fn from_shared_gate(gate: Arc<PyGate>) -> Self {
+        let ptr = NonNull::new(Arc::into_raw(gate).cast_mut())
+            .unwrap()
+            .cast::<()>();
+        Self(
+            PointerBits::new(PackedOperationType::PyGatePointerShared)
+                .with_pointer(ptr)
+                .into(),
+        )
+    }
+
     /// Construct a new `PackedOperation` from an owned heap-allocated `PyInstruction`.
     pub fn from_instruction(instruction: Box<PyInstruction>) -> Self {
         let ptr = NonNull::from(Box::leak(instruction)).cast::<()>();
@@ -539,7 +885,26 @@ impl PackedOperation {
         )
     }
 
+    /// Construct a new `PackedOperation` that shares ownership of `instruction` with any other
+    /// `PackedOperation` created from the same `Arc`, via reference counting rather than a deep
+    /// copy. Use [`Self::make_unique`] to detach a private copy before mutating it.
+    pub fn from_shared_instruction(instruction: Arc<PyInstruction>) -> Self {
+        let ptr = NonNull::new(Arc::into_raw(instruction).cast_mut())
+            .unwrap()
+            .cast::<()>();
+        Self(
+            PointerBits::new(PackedOperationType::PyInstructionPointerShared)
+                .with_pointer(ptr)
+                .into(),
+        )
+    }
+
     /// Construct a new `PackedOperation` from an owned heap-allocated `PyOperation`.
+    ///
+    /// Unlike [`Self::from_gate`]/[`Self::from_instruction`], there's no `from_shared_operation`
+    /// counterpart: the 3-bit `discriminant` field (see `BitField::_CHECK`) only has two spare
+    /// values left once every non-shared variant is accounted for, and those are spent on the
+    /// more commonly duplicated `PyGate`/`PyInstruction` cases instead.
     pub fn from_operation(operation: Box<PyOperation>) -> Self {
         let ptr = NonNull::from(Box::leak(operation)).cast::<()>();
         Self(
@@ -575,6 +940,11 @@ impl PackedOperation {
         py: Python<'py>,
         memo: Option<&Bound<'py, PyDict>>,
     ) -> PyResult<Self> {
+        if self.discriminant() == PackedOperationType::StandardGateInlineAngle {
+            // No Python object is involved, so a deep copy is just a bit-for-bit copy that
+            // preserves the inline angle; going through `standard.into()` would silently drop it.
+            return Ok(Self(unsafe { self.0.angle }.into()));
+        }
         let deepcopy = DEEPCOPY.get_bound(py);
         match self.view() {
             OperationRef::Standard(standard) => Ok(standard.into()),
@@ -612,6 +982,11 @@ impl PackedOperation {
     /// Copy this operation, including a Python-space call to `copy` on the `Operation` subclass, if
     /// any.
     pub fn py_copy(&self, py: Python) -> PyResult<Self> {
+        if self.discriminant() == PackedOperationType::StandardGateInlineAngle {
+            // No Python object is involved, so a copy is just a bit-for-bit copy that preserves
+            // the inline angle; going through `standard.into()` would silently drop it.
+            return Ok(Self(unsafe { self.0.angle }.into()));
+        }
         let copy_attr = intern!(py, "copy");
         match self.view() {
             OperationRef::Standard(standard) => Ok(standard.into()),
@@ -664,7 +1039,7 @@ impl PackedOperation {
                         .get_bound(py)
                         .downcast::<PyType>()?
                         .is_subclass(py_type),
-                    StandardInstruction::Delay(_) => DELAY
+                    StandardInstruction::Delay(_, _) => DELAY
                         .get_bound(py)
                         .downcast::<PyType>()?
                         .is_subclass(py_type),
@@ -684,6 +1059,300 @@ impl PackedOperation {
         };
         py_op.is_instance(py_type)
     }
+
+    /// Append the canonical byte encoding of this operation onto `buf`.
+    ///
+    /// The format is a one-byte tag mirroring `PackedOperationType`, followed by:
+    ///
+    /// * `StandardGate`: a single opcode byte (the `StandardGate` discriminant).
+    /// * `StandardGateInlineAngle`: the opcode byte, then the 4-byte little-endian bits of the
+    ///   inline `f32` angle.
+    /// * `StandardInstruction`: a `StandardInstructionType` byte, then its 4-byte little-endian
+    ///   immediate payload (the barrier qubit count, or the delay's packed duration and unit).
+    /// * the pointer variants (`PyGate`/`PyInstruction`/`PyOperation`): a 4-byte little-endian
+    ///   length prefix, the operation's name as UTF-8, its qubit/clbit/param counts as three
+    ///   further 4-byte little-endian integers, a one-byte control-flow flag, and finally a
+    ///   4-byte little-endian length prefix followed by a `pickle` of the underlying Python
+    ///   object, which is what lets `decode` actually reconstruct it.
+    ///
+    /// This gives a zero-Python, zero-copy-friendly wire format for the common case of standard
+    /// gates and instructions, which is far faster than pickling `CircuitInstruction`s; a custom
+    /// operation still needs the interpreter to come back, the same as it would with pickle, but
+    /// it's carried inline so a stream of instructions stays self-contained. See `decode` for the
+    /// corresponding reader.
+    ///
+    /// A `StandardInstruction::Delay` whose duration can't be packed into the inline 24-bit field
+    /// never reaches the `StandardInstruction` branch below: `PackedOperation::
+    /// from_standard_instruction` already falls back to a boxed `PyInstruction` for it, which
+    /// encodes via the ordinary pointer-variant path instead.
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        match self.view() {
+            OperationRef::Standard(standard) => {
+                if let Some(angle) = self.try_inline_angle() {
+                    buf.push(PackedOperationType::StandardGateInlineAngle as u8);
+                    buf.push(standard as u8);
+                    buf.extend_from_slice(&angle.to_le_bytes());
+                } else {
+                    buf.push(PackedOperationType::StandardGate as u8);
+                    buf.push(standard as u8);
+                }
+            }
+            OperationRef::StandardInstruction(instruction) => {
+                let (instruction_type, payload) = match instruction {
+                    StandardInstruction::Barrier(num_qubits) => {
+                        let num_qubits: u32 = num_qubits.try_into().expect(
+                            "The PackedOperation representation currently requires barrier size to be <= 32 bits."
+                        );
+                        (StandardInstructionType::Barrier, num_qubits)
+                    }
+                    StandardInstruction::Delay(duration, unit) => {
+                        let payload = inline_delay_duration(&duration)
+                            .and_then(|ticks| ImmediateValue::from_delay(ticks, unit))
+                            .expect(
+                                "unreachable: non-inlinable delays are stored as a boxed \
+                                 PyInstruction instead, see from_standard_instruction",
+                            );
+                        (StandardInstructionType::Delay, payload.u32())
+                    }
+                    StandardInstruction::Measure => (StandardInstructionType::Measure, 0),
+                    StandardInstruction::Reset => (StandardInstructionType::Reset, 0),
+                };
+                buf.push(PackedOperationType::StandardInstruction as u8);
+                buf.push(instruction_type as u8);
+                buf.extend_from_slice(&payload.to_le_bytes());
+            }
+            OperationRef::Gate(gate) => Python::with_gil(|py| {
+                Self::encode_pointer_operation(
+                    buf,
+                    PackedOperationType::PyGatePointer,
+                    gate,
+                    gate.gate.bind(py),
+                )
+            }),
+            OperationRef::Instruction(instruction) => Python::with_gil(|py| {
+                Self::encode_pointer_operation(
+                    buf,
+                    PackedOperationType::PyInstructionPointer,
+                    instruction,
+                    instruction.instruction.bind(py),
+                )
+            }),
+            OperationRef::Operation(operation) => Python::with_gil(|py| {
+                Self::encode_pointer_operation(
+                    buf,
+                    PackedOperationType::PyOperationPointer,
+                    operation,
+                    operation.operation.bind(py),
+                )
+            }),
+        }
+    }
+
+    /// Shared tail of `encode` for the three pointer variants: a length-prefixed name, the
+    /// qubit/clbit/param counts and control-flow flag, and finally a length-prefixed `pickle` of
+    /// `py_obj` itself. Carrying the pickle inline (rather than just the header) is what lets
+    /// `decode` actually reconstruct a working `PyGate`/`PyInstruction`/`PyOperation` instead of
+    /// only identifying one.
+    fn encode_pointer_operation(
+        buf: &mut Vec<u8>,
+        tag: PackedOperationType,
+        op: &impl Operation,
+        py_obj: &Bound<PyAny>,
+    ) {
+        buf.push(tag as u8);
+        let name = op.name().as_bytes();
+        let name_len: u32 = name
+            .len()
+            .try_into()
+            .expect("operation name too long to encode");
+        buf.extend_from_slice(&name_len.to_le_bytes());
+        buf.extend_from_slice(name);
+        buf.extend_from_slice(&op.num_qubits().to_le_bytes());
+        buf.extend_from_slice(&op.num_clbits().to_le_bytes());
+        buf.extend_from_slice(&op.num_params().to_le_bytes());
+        buf.push(op.control_flow() as u8);
+
+        let py = py_obj.py();
+        let pickled = PICKLE_DUMPS
+            .get_bound(py)
+            .call1((py_obj,))
+            .expect("a custom Gate/Instruction/Operation subclass must be picklable to use the byte codec");
+        let pickled: &[u8] = pickled
+            .downcast::<PyBytes>()
+            .expect("pickle.dumps must return bytes")
+            .as_bytes();
+        let pickled_len: u32 = pickled
+            .len()
+            .try_into()
+            .expect("pickled operation too large to encode");
+        buf.extend_from_slice(&pickled_len.to_le_bytes());
+        buf.extend_from_slice(pickled);
+    }
+
+    /// Decode a `PackedOperation` previously written by `encode`, returning it along with the
+    /// number of bytes consumed from the front of `bytes`, so a stream of instructions can be
+    /// parsed in a tight loop.
+    ///
+    /// Standard gates and standard instructions decode fully from this byte stream alone,
+    /// without touching Python at all. The pointer variants carry their own pickle of the
+    /// original Python object, so they do round-trip, but doing so takes the GIL and unpickles,
+    /// the same cost pickling `CircuitInstruction`s directly would have paid for that operation.
+    ///
+    /// # Panics
+    ///
+    /// Panics (via ordinary bounds-checked slice indexing, never via an out-of-bounds read) if
+    /// `bytes` is truncated.
+    pub fn decode(bytes: &[u8]) -> (PackedOperation, usize) {
+        let tag = PackedOperationType::from_bits(bytes[0]);
+        match tag {
+            PackedOperationType::StandardGate => {
+                let gate = StandardGate::from_bits(bytes[1]);
+                (PackedOperation::from_standard(gate), 2)
+            }
+            PackedOperationType::StandardGateInlineAngle => {
+                let gate = StandardGate::from_bits(bytes[1]);
+                let angle = f32::from_le_bytes(bytes[2..6].try_into().unwrap());
+                (PackedOperation::from_standard_inline_angle(gate, angle), 6)
+            }
+            PackedOperationType::StandardInstruction => {
+                let instruction_type = StandardInstructionType::from_bits(bytes[1]);
+                let payload = u32::from_le_bytes(bytes[2..6].try_into().unwrap());
+                let instruction = match instruction_type {
+                    StandardInstructionType::Barrier => {
+                        StandardInstruction::Barrier(payload as usize)
+                    }
+                    StandardInstructionType::Delay => {
+                        let payload = ImmediateValue::from_bits(payload);
+                        StandardInstruction::Delay(
+                            Param::Float(payload.delay_duration() as f64),
+                            payload.delay_unit(),
+                        )
+                    }
+                    StandardInstructionType::Measure => StandardInstruction::Measure,
+                    StandardInstructionType::Reset => StandardInstruction::Reset,
+                };
+                (PackedOperation::from_standard_instruction(instruction), 6)
+            }
+            PackedOperationType::PyGatePointer
+            | PackedOperationType::PyInstructionPointer
+            | PackedOperationType::PyOperationPointer => {
+                let name_len = u32::from_le_bytes(bytes[1..5].try_into().unwrap()) as usize;
+                let name_end = 5 + name_len;
+                let op_name = std::str::from_utf8(&bytes[5..name_end])
+                    .expect("operation name must be valid UTF-8")
+                    .to_string();
+                let qubits = u32::from_le_bytes(bytes[name_end..name_end + 4].try_into().unwrap());
+                let clbits =
+                    u32::from_le_bytes(bytes[name_end + 4..name_end + 8].try_into().unwrap());
+                let params =
+                    u32::from_le_bytes(bytes[name_end + 8..name_end + 12].try_into().unwrap());
+                let control_flow = bytes[name_end + 12] != 0;
+                let pickled_len_start = name_end + 13;
+                let pickled_len = u32::from_le_bytes(
+                    bytes[pickled_len_start..pickled_len_start + 4]
+                        .try_into()
+                        .unwrap(),
+                ) as usize;
+                let pickled_start = pickled_len_start + 4;
+                let pickled_end = pickled_start + pickled_len;
+                let pickled = &bytes[pickled_start..pickled_end];
+
+                let operation: PyObject = Python::with_gil(|py| {
+                    PICKLE_LOADS
+                        .get_bound(py)
+                        .call1((PyBytes::new_bound(py, pickled),))
+                        .expect("pickled operation must unpickle back into a live Python object")
+                        .unbind()
+                });
+                let packed = match tag {
+                    PackedOperationType::PyGatePointer => {
+                        PackedOperation::from_gate(Box::new(PyGate {
+                            gate: operation,
+                            qubits,
+                            clbits,
+                            params,
+                            op_name,
+                        }))
+                    }
+                    PackedOperationType::PyInstructionPointer => {
+                        PackedOperation::from_instruction(Box::new(PyInstruction {
+                            instruction: operation,
+                            qubits,
+                            clbits,
+                            params,
+                            control_flow,
+                            op_name,
+                        }))
+                    }
+                    PackedOperationType::PyOperationPointer => {
+                        PackedOperation::from_operation(Box::new(PyOperation {
+                            operation,
+                            qubits,
+                            clbits,
+                            params,
+                            op_name,
+                        }))
+                    }
+                    _ => unreachable!("matched above"),
+                };
+                (packed, pickled_end)
+            }
+        }
+    }
+
+    /// Classify what kind of operation this is.
+    ///
+    /// For the inline discriminants this resolves from a static table keyed on the inline opcode
+    /// byte and never touches Python. For a boxed `PyGate` it falls back to checking whether the
+    /// wrapped Python object's class is one of the imported standard-gate classes (e.g. a
+    /// `ControlledGate` that's been mutated away from its default state, such as one with a
+    /// custom `ctrl_state`, is still represented as a `PyGate` rather than an inline
+    /// `StandardGate`, but its class can still identify it); if so its category comes from the
+    /// same `STDGATE_CATEGORIES` table as an inline `StandardGate`. `PyInstruction`/`PyOperation`
+    /// have no equivalent "standard" class registry to fall back to, so they're always `Custom`.
+    pub fn category(&self) -> OpCategory {
+        match self.view() {
+            OperationRef::Standard(standard) => STDGATE_CATEGORIES[standard as usize],
+            OperationRef::StandardInstruction(instruction) => match instruction {
+                StandardInstruction::Barrier(_) => OpCategory::Barrier,
+                StandardInstruction::Delay(_, _) => OpCategory::Delay,
+                StandardInstruction::Measure => OpCategory::Measurement,
+                StandardInstruction::Reset => OpCategory::Reset,
+            },
+            OperationRef::Gate(gate) => Python::with_gil(|py| {
+                try_standard_gate_from_py(py, gate.gate.bind(py))
+                    .map(|standard| STDGATE_CATEGORIES[standard as usize])
+                    .unwrap_or(OpCategory::Custom)
+            }),
+            OperationRef::Instruction(_) | OperationRef::Operation(_) => OpCategory::Custom,
+        }
+    }
+
+    /// Is this operation a member of the Clifford group?
+    #[inline]
+    pub fn is_clifford(&self) -> bool {
+        self.category() == OpCategory::CliffordGate
+    }
+
+    /// Is this operation a projective measurement?
+    #[inline]
+    pub fn is_measurement(&self) -> bool {
+        self.category() == OpCategory::Measurement
+    }
+
+    /// Does this operation redirect control flow (e.g. `IfElseOp`, `ForLoopOp`, `WhileLoopOp`)?
+    #[inline]
+    pub fn is_control_flow(&self) -> bool {
+        self.control_flow()
+    }
+
+    /// Does this operation read or write any clbits?
+    #[inline]
+    pub fn touches_clbits(&self) -> bool {
+        self.category() == OpCategory::Measurement
+            || self.is_control_flow()
+            || self.num_clbits() > 0
+    }
 }
 
 impl Operation for PackedOperation {
@@ -751,6 +1420,13 @@ impl From<StandardGate> for PackedOperation {
     }
 }
 
+impl From<(StandardGate, &[Param])> for PackedOperation {
+    #[inline]
+    fn from((standard, params): (StandardGate, &[Param])) -> Self {
+        Self::from_standard_with_params(standard, params)
+    }
+}
+
 impl From<StandardInstruction> for PackedOperation {
     #[inline]
     fn from(value: StandardInstruction) -> Self {
@@ -779,8 +1455,83 @@ impl_packed_operation_from_py!(PyGate, PackedOperation::from_gate);
 impl_packed_operation_from_py!(PyInstruction, PackedOperation::from_instruction);
 impl_packed_operation_from_py!(PyOperation, PackedOperation::from_operation);
 
+impl PackedOperation {
+    /// Whether this holds a pointer variant whose allocation is shared via `Arc` rather than
+    /// uniquely owned via `Box`. Only meaningful when `self.discriminant()` is one of the
+    /// `Py*Pointer` variants.
+    #[inline]
+    fn is_shared(&self) -> bool {
+        unsafe { self.0.pointer }.is_shared()
+    }
+
+    /// If this operation's payload is a shared (`Arc`-backed) pointer with other outstanding
+    /// references, detach it by cloning the underlying Python-side object into a privately owned
+    /// copy. No-op for owned payloads, and for shared payloads that are already uniquely held.
+    ///
+    /// Callers must do this before mutating through a pointer obtained from this operation, since
+    /// a shared payload may be aliased by other `PackedOperation`s.
+    pub fn make_unique(&mut self) {
+        fn make_unique_as<T: Clone>(slf: &mut PackedOperation, owned_tag: PackedOperationType) {
+            let pointer = unsafe { slf.0.pointer }.pointer();
+            // SAFETY: a shared payload's pointer always originated from `Arc::into_raw`.
+            let shared = unsafe { Arc::from_raw(pointer.cast::<T>().as_ptr()) };
+            if Arc::strong_count(&shared) == 1 {
+                // We're the only handle left; nothing to detach. Forget the temporary `Arc`
+                // again rather than letting it decrement the (already-unique) refcount.
+                std::mem::forget(shared);
+                return;
+            }
+            let private = Box::new((*shared).clone());
+            let ptr = NonNull::from(Box::leak(private)).cast::<()>();
+            slf.0 = PointerBits::new(owned_tag).with_pointer(ptr).into();
+        }
+
+        match self.discriminant() {
+            PackedOperationType::PyGatePointerShared => {
+                make_unique_as::<PyGate>(self, PackedOperationType::PyGatePointer)
+            }
+            PackedOperationType::PyInstructionPointerShared => {
+                make_unique_as::<PyInstruction>(self, PackedOperationType::PyInstructionPointer)
+            }
+            _ => (),
+        }
+    }
+}
+
 impl Clone for PackedOperation {
     fn clone(&self) -> Self {
+        fn clone_shared_as<T>(slf: &PackedOperation) -> NonNull<()> {
+            let pointer = unsafe { slf.0.pointer }.pointer();
+            // SAFETY: a shared payload's pointer always originated from `Arc::into_raw`, and
+            // `slf` keeps the allocation alive for at least as long as this call.
+            let shared = unsafe { Arc::from_raw(pointer.cast::<T>().as_ptr()) };
+            let bumped = Arc::clone(&shared);
+            std::mem::forget(shared);
+            NonNull::new(Arc::into_raw(bumped).cast_mut()).unwrap().cast::<()>()
+        }
+
+        let tag = self.discriminant();
+        if tag == PackedOperationType::StandardGateInlineAngle {
+            // No Python object is involved, so cloning is just a bit-for-bit copy that
+            // preserves the inline angle; going through `view()` would silently drop it.
+            return Self(unsafe { self.0.angle }.into());
+        }
+        if matches!(
+            tag,
+            PackedOperationType::PyGatePointerShared
+                | PackedOperationType::PyInstructionPointerShared
+        ) {
+            // A shared payload is cheap to clone: bump the `Arc` refcount instead of deep-copying
+            // the Python object behind a fresh `Box` allocation.
+            let ptr = match tag {
+                PackedOperationType::PyGatePointerShared => clone_shared_as::<PyGate>(self),
+                PackedOperationType::PyInstructionPointerShared => {
+                    clone_shared_as::<PyInstruction>(self)
+                }
+                _ => unreachable!("only shared pointer variants reach here"),
+            };
+            return Self(PointerBits::new(tag).with_pointer(ptr).into());
+        }
         match self.view() {
             OperationRef::Standard(standard) => Self::from_standard(standard),
             OperationRef::StandardInstruction(instruction) => {
@@ -800,19 +1551,31 @@ impl Drop for PackedOperation {
     fn drop(&mut self) {
         fn drop_pointer_as<T>(slf: &mut PackedOperation) {
             let pointer = unsafe { slf.0.pointer }.pointer();
+            let shared = unsafe { slf.0.pointer }.is_shared();
 
             // SAFETY: `PackedOperation` asserts ownership over its contents, and the contained
             // pointer can only be null if we were already dropped.  We set our discriminant to mark
             // ourselves as plain old data immediately just as a defensive measure.
-            let boxed = unsafe { Box::from_raw(pointer.cast::<T>().as_ptr()) };
             slf.0 = StandardGateBits::new().into();
-            ::std::mem::drop(boxed);
+            if shared {
+                // SAFETY: a shared payload's pointer always originated from `Arc::into_raw`.
+                ::std::mem::drop(unsafe { Arc::from_raw(pointer.cast::<T>().as_ptr()) });
+            } else {
+                ::std::mem::drop(unsafe { Box::from_raw(pointer.cast::<T>().as_ptr()) });
+            }
         }
 
         match self.discriminant() {
-            PackedOperationType::StandardGate | PackedOperationType::StandardInstruction => (),
-            PackedOperationType::PyGatePointer => drop_pointer_as::<PyGate>(self),
-            PackedOperationType::PyInstructionPointer => drop_pointer_as::<PyInstruction>(self),
+            PackedOperationType::StandardGate
+            | PackedOperationType::StandardInstruction
+            | PackedOperationType::StandardGateInlineAngle => (),
+            PackedOperationType::PyGatePointer | PackedOperationType::PyGatePointerShared => {
+                drop_pointer_as::<PyGate>(self)
+            }
+            PackedOperationType::PyInstructionPointer
+            | PackedOperationType::PyInstructionPointerShared => {
+                drop_pointer_as::<PyInstruction>(self)
+            }
             PackedOperationType::PyOperationPointer => drop_pointer_as::<PyOperation>(self),
         }
     }
@@ -971,3 +1734,413 @@ impl PackedInstruction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_angle_round_trips() {
+        for angle in [0.0_f32, 1.0, -1.0, std::f32::consts::PI, f32::MIN, f32::MAX] {
+            let op = PackedOperation::from_standard_inline_angle(StandardGate::RXGate, angle);
+            assert_eq!(op.try_inline_angle().unwrap().to_bits(), angle.to_bits());
+            assert_eq!(op.standard_gate(), StandardGate::RXGate);
+        }
+    }
+
+    #[test]
+    fn inline_angle_round_trips_edge_cases() {
+        let edge_cases = [
+            0.0_f32,
+            -0.0_f32,
+            f32::NAN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::from_bits(1), // smallest positive subnormal
+            -f32::from_bits(1),
+        ];
+        for angle in edge_cases {
+            let op = PackedOperation::from_standard_inline_angle(StandardGate::RZGate, angle);
+            let recovered = op.try_inline_angle().unwrap();
+            // Bit-exact comparison, since `NAN != NAN` under `==`.
+            assert_eq!(recovered.to_bits(), angle.to_bits());
+        }
+    }
+
+    #[test]
+    fn zeroed_bits_still_decode_as_standard_gate() {
+        // A `PackedOperation` that's been zeroed out byte-for-byte (e.g. uninitialised memory)
+        // must never be mistaken for an owned pointer; it has to fall back to being interpreted
+        // as a `StandardGate`, which can't dangling-dereference.
+        let zeroed = PackedOperation(StandardGateBits::new().into());
+        assert_eq!(zeroed.discriminant(), PackedOperationType::StandardGate);
+        assert!(zeroed.try_standard_gate().is_some());
+        assert!(zeroed.try_inline_angle().is_none());
+    }
+
+    #[test]
+    fn from_standard_with_params_packs_a_single_concrete_angle_inline() {
+        let op =
+            PackedOperation::from_standard_with_params(StandardGate::RXGate, &[Param::Float(0.5)]);
+        assert_eq!(op.discriminant(), PackedOperationType::StandardGateInlineAngle);
+        assert_eq!(op.try_inline_angle(), Some(0.5));
+    }
+
+    #[test]
+    fn from_standard_with_params_falls_back_for_symbolic_or_multi_param_gates() {
+        // Zero parameters (e.g. `HGate`): nothing to pack inline.
+        let op = PackedOperation::from_standard_with_params(StandardGate::HGate, &[]);
+        assert_eq!(op.discriminant(), PackedOperationType::StandardGate);
+        assert!(op.try_inline_angle().is_none());
+
+        // More than one parameter (e.g. `UGate`): the inline word only has room for one angle.
+        let op = PackedOperation::from_standard_with_params(
+            StandardGate::UGate,
+            &[Param::Float(0.1), Param::Float(0.2), Param::Float(0.3)],
+        );
+        assert_eq!(op.discriminant(), PackedOperationType::StandardGate);
+        assert!(op.try_inline_angle().is_none());
+
+        // A symbolic `ParameterExpression`: not a concrete value to pack.
+        Python::with_gil(|py| {
+            let expr = Param::ParameterExpression(py.None());
+            let op = PackedOperation::from_standard_with_params(StandardGate::RXGate, &[expr]);
+            assert_eq!(op.discriminant(), PackedOperationType::StandardGate);
+            assert!(op.try_inline_angle().is_none());
+        });
+    }
+
+    #[test]
+    fn standard_gate_encode_decode_round_trips() {
+        for i in 0..STANDARD_GATE_SIZE as u8 {
+            let gate = StandardGate::from_bits(i);
+            let op = PackedOperation::from_standard(gate);
+            let mut buf = Vec::new();
+            op.encode(&mut buf);
+            let (decoded, consumed) = PackedOperation::decode(&buf);
+            assert_eq!(consumed, buf.len());
+            assert_eq!(decoded.standard_gate(), gate);
+        }
+    }
+
+    #[test]
+    fn standard_gate_inline_angle_encode_decode_round_trips() {
+        for angle in [0.0_f32, -0.0, 1.5, f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let op = PackedOperation::from_standard_inline_angle(StandardGate::RXGate, angle);
+            let mut buf = Vec::new();
+            op.encode(&mut buf);
+            let (decoded, consumed) = PackedOperation::decode(&buf);
+            assert_eq!(consumed, buf.len());
+            assert_eq!(
+                decoded.try_inline_angle().unwrap().to_bits(),
+                angle.to_bits()
+            );
+            assert_eq!(decoded.standard_gate(), StandardGate::RXGate);
+        }
+    }
+
+    #[test]
+    fn standard_instruction_encode_decode_round_trips() {
+        for instruction in [
+            StandardInstruction::Barrier(0),
+            StandardInstruction::Barrier(3),
+            StandardInstruction::Delay(Param::Float(0.0), DelayUnit::DT),
+            StandardInstruction::Delay(Param::Float(1000.0), DelayUnit::NS),
+            StandardInstruction::Measure,
+            StandardInstruction::Reset,
+        ] {
+            let op = PackedOperation::from_standard_instruction(instruction.clone());
+            let mut buf = Vec::new();
+            op.encode(&mut buf);
+            let (decoded, consumed) = PackedOperation::decode(&buf);
+            assert_eq!(consumed, buf.len());
+            assert_eq!(decoded.standard_instruction(), instruction);
+        }
+    }
+
+    #[test]
+    fn delay_inline_duration_round_trips_all_units() {
+        for unit in [
+            DelayUnit::DT,
+            DelayUnit::NS,
+            DelayUnit::US,
+            DelayUnit::MS,
+            DelayUnit::S,
+            DelayUnit::PS,
+        ] {
+            for duration in [0_u32, 1, 42, ImmediateValue::MAX_INLINE_DELAY_DURATION] {
+                let instruction = StandardInstruction::Delay(Param::Float(duration as f64), unit);
+
+                let op = PackedOperation::from_standard_instruction(instruction.clone());
+                assert_eq!(op.standard_instruction(), instruction);
+
+                let mut buf = Vec::new();
+                op.encode(&mut buf);
+                let (decoded, consumed) = PackedOperation::decode(&buf);
+                assert_eq!(consumed, buf.len());
+                assert_eq!(decoded.standard_instruction(), instruction);
+            }
+        }
+    }
+
+    #[test]
+    fn delay_duration_too_large_to_inline_falls_back_to_boxed_instruction() {
+        let too_big = ImmediateValue::MAX_INLINE_DELAY_DURATION as f64 + 1.0;
+        let instruction = StandardInstruction::Delay(Param::Float(too_big), DelayUnit::DT);
+        let op = PackedOperation::from_standard_instruction(instruction);
+        assert!(matches!(op.view(), OperationRef::Instruction(_)));
+        assert_eq!(op.name(), "delay");
+    }
+
+    #[test]
+    fn delay_duration_non_integral_falls_back_to_boxed_instruction() {
+        let instruction = StandardInstruction::Delay(Param::Float(1.5), DelayUnit::DT);
+        let op = PackedOperation::from_standard_instruction(instruction);
+        assert!(matches!(op.view(), OperationRef::Instruction(_)));
+        assert_eq!(op.name(), "delay");
+    }
+
+    #[test]
+    fn decode_consumes_exactly_one_entry_from_a_stream() {
+        let ops = [
+            PackedOperation::from_standard(StandardGate::HGate),
+            PackedOperation::from_standard_inline_angle(StandardGate::RZGate, 0.25),
+            PackedOperation::from_standard_instruction(StandardInstruction::Measure),
+        ];
+        let mut buf = Vec::new();
+        for op in &ops {
+            op.encode(&mut buf);
+        }
+        let mut offset = 0;
+        for op in &ops {
+            let (decoded, consumed) = PackedOperation::decode(&buf[offset..]);
+            assert_eq!(decoded.try_standard_gate(), op.try_standard_gate());
+            assert_eq!(
+                decoded.try_inline_angle().map(f32::to_bits),
+                op.try_inline_angle().map(f32::to_bits)
+            );
+            assert_eq!(
+                decoded.try_standard_instruction(),
+                op.try_standard_instruction()
+            );
+            offset += consumed;
+        }
+        assert_eq!(offset, buf.len());
+    }
+
+    #[test]
+    fn pointer_variant_round_trips_through_pickle() {
+        Python::with_gil(|py| {
+            let payload = pyo3::types::PyList::new_bound(py, [1, 2, 3])
+                .into_any()
+                .unbind();
+            let op = PackedOperation::from_gate(Box::new(PyGate {
+                gate: payload,
+                qubits: 2,
+                clbits: 0,
+                params: 0,
+                op_name: "my_custom_gate".to_string(),
+            }));
+
+            let mut buf = Vec::new();
+            op.encode(&mut buf);
+            let (decoded, consumed) = PackedOperation::decode(&buf);
+            assert_eq!(consumed, buf.len());
+            assert_eq!(decoded.name(), "my_custom_gate");
+            assert_eq!(decoded.num_qubits(), 2);
+
+            match decoded.view() {
+                OperationRef::Gate(gate) => {
+                    let roundtripped: Vec<i64> = gate.gate.bind(py).extract().unwrap();
+                    assert_eq!(roundtripped, vec![1, 2, 3]);
+                }
+                _ => panic!("expected decode to reconstruct a PyGate"),
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn decode_rejects_truncated_pointer_header() {
+        // A pointer-variant tag with a length prefix claiming more bytes than are actually
+        // present must be rejected via an ordinary bounds-checked panic, not an out-of-bounds
+        // read.
+        let buf = vec![PackedOperationType::PyGatePointer as u8, 0xff, 0xff, 0xff, 0x7f];
+        let _ = PackedOperation::decode(&buf);
+    }
+
+    #[test]
+    fn standard_gate_category_table_covers_every_variant() {
+        for i in 0..STANDARD_GATE_SIZE as u8 {
+            let gate = StandardGate::from_bits(i);
+            let op = PackedOperation::from_standard(gate);
+            // Every `StandardGate` must classify as a gate category, never as an instruction-only
+            // category like `Measurement`/`Reset`/`Barrier`/`Delay`/`Custom`.
+            assert!(matches!(
+                op.category(),
+                OpCategory::CliffordGate | OpCategory::Rotation | OpCategory::OtherGate
+            ));
+        }
+    }
+
+    #[test]
+    fn standard_gate_category_table_classifies_known_gates_correctly() {
+        // Clifford gates: generated by H, S and CX (plus Paulis), and anything locally
+        // equivalent to one of those generators.
+        for gate in [
+            StandardGate::HGate,
+            StandardGate::SGate,
+            StandardGate::CXGate,
+            StandardGate::SwapGate,
+            StandardGate::DCXGate, // CX(0,1) . CX(1,0): a product of Cliffords is Clifford.
+            StandardGate::ECRGate, // locally equivalent to CX.
+            StandardGate::ISwapGate,
+        ] {
+            assert_eq!(
+                PackedOperation::from_standard(gate).category(),
+                OpCategory::CliffordGate,
+                "{gate:?} should classify as Clifford"
+            );
+        }
+        // Non-Clifford gates: T/Tdg and the controlled-S family sit at the third level of the
+        // Clifford hierarchy, and CCX/CCZ (Toffoli and its Hadamard-conjugate) are the canonical
+        // non-Clifford multi-controlled resource gates.
+        for gate in [
+            StandardGate::TGate,
+            StandardGate::TdgGate,
+            StandardGate::CSGate,
+            StandardGate::CSdgGate,
+            StandardGate::CCXGate,
+            StandardGate::CCZGate,
+        ] {
+            assert_ne!(
+                PackedOperation::from_standard(gate).category(),
+                OpCategory::CliffordGate,
+                "{gate:?} should not classify as Clifford"
+            );
+        }
+    }
+
+    #[test]
+    fn standard_instruction_categories_match_their_variant() {
+        assert_eq!(
+            PackedOperation::from_standard_instruction(StandardInstruction::Barrier(2)).category(),
+            OpCategory::Barrier
+        );
+        assert_eq!(
+            PackedOperation::from_standard_instruction(StandardInstruction::Measure).category(),
+            OpCategory::Measurement
+        );
+        assert_eq!(
+            PackedOperation::from_standard_instruction(StandardInstruction::Reset).category(),
+            OpCategory::Reset
+        );
+    }
+
+    #[test]
+    fn clifford_and_measurement_predicates_agree_with_category() {
+        let h = PackedOperation::from_standard(StandardGate::HGate);
+        assert!(h.is_clifford());
+        assert!(!h.is_measurement());
+
+        let measure = PackedOperation::from_standard_instruction(StandardInstruction::Measure);
+        assert!(measure.is_measurement());
+        assert!(!measure.is_clifford());
+        assert!(measure.touches_clbits());
+
+        let reset = PackedOperation::from_standard_instruction(StandardInstruction::Reset);
+        assert!(!reset.touches_clbits());
+
+        assert!(!h.touches_clbits());
+    }
+
+    // `PointerBits` stores the address in a `u64` regardless of host pointer width, so these
+    // round-trips exercise the same code path a 32-bit/`wasm32` build would take: a pointer value
+    // that only occupies the low 32 bits.
+    #[cfg(target_pointer_width = "32")]
+    mod pointer_width_32 {
+        use super::*;
+
+        #[test]
+        fn pointer_bits_round_trip_a_32_bit_address() {
+            let addr = 0xdead_bee0_u64; // 8-byte aligned, fits in 32 bits.
+            let ptr = NonNull::new(addr as *mut ()).unwrap();
+            let bits = PointerBits::new(PackedOperationType::PyGatePointer).with_pointer(ptr);
+            assert_eq!(bits.pointer(), ptr);
+            assert_eq!(bits.discriminant(), PackedOperationType::PyGatePointer);
+            assert!(!bits.is_shared());
+
+            let shared_bits =
+                PointerBits::new(PackedOperationType::PyGatePointerShared).with_pointer(ptr);
+            assert_eq!(shared_bits.pointer(), ptr);
+            assert!(shared_bits.is_shared());
+        }
+    }
+
+    #[test]
+    fn make_unique_is_a_no_op_for_non_pointer_operations() {
+        let mut gate = PackedOperation::from_standard(StandardGate::XGate);
+        gate.make_unique();
+        assert_eq!(gate.standard_gate(), StandardGate::XGate);
+
+        let mut angle = PackedOperation::from_standard_inline_angle(StandardGate::RXGate, 0.5);
+        angle.make_unique();
+        assert_eq!(angle.try_inline_angle().unwrap(), 0.5);
+
+        let mut instruction =
+            PackedOperation::from_standard_instruction(StandardInstruction::Measure);
+        instruction.make_unique();
+        assert_eq!(instruction.standard_instruction(), StandardInstruction::Measure);
+    }
+
+    /// `PackedOperation`'s shared pointer variants move ownership around by type-erasing an
+    /// `Arc<T>` through a `NonNull<()>` and reconstituting it with `Arc::from_raw`/`Arc::clone`,
+    /// exactly as `PackedOperation::clone`/`drop`/`make_unique` do for `PyGate` & friends. `PyGate`
+    /// itself needs a live Python interpreter to construct, so this exercises the same
+    /// pointer-erasure mechanics with a plain Rust type whose destructor we can observe directly.
+    #[test]
+    fn shared_pointer_refcount_mechanics_have_no_double_free() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounter<'a>(&'a AtomicUsize);
+        impl Clone for DropCounter<'_> {
+            fn clone(&self) -> Self {
+                Self(self.0)
+            }
+        }
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let original = Arc::new(DropCounter(&drops));
+
+        // Emulate `PackedOperation::from_shared_gate`: type-erase the `Arc` into a `NonNull<()>`.
+        let ptr_a = NonNull::new(Arc::into_raw(Arc::clone(&original)).cast_mut())
+            .unwrap()
+            .cast::<()>();
+
+        // Emulate `PackedOperation::clone` on a shared payload: reconstitute, bump, re-erase.
+        let handle = unsafe { Arc::from_raw(ptr_a.cast::<DropCounter>().as_ptr()) };
+        let bumped = Arc::clone(&handle);
+        std::mem::forget(handle);
+        let ptr_b = NonNull::new(Arc::into_raw(bumped).cast_mut())
+            .unwrap()
+            .cast::<()>();
+
+        assert_eq!(Arc::strong_count(&original), 3);
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+        // Emulate `PackedOperation::drop` for each of the two erased handles.
+        drop(unsafe { Arc::from_raw(ptr_a.cast::<DropCounter>().as_ptr()) });
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(unsafe { Arc::from_raw(ptr_b.cast::<DropCounter>().as_ptr()) });
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+        // The last strong reference is `original`; its drop must run the destructor exactly once.
+        drop(original);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+}